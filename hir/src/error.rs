@@ -0,0 +1,63 @@
+use std::fmt;
+
+use parser::location::Location;
+
+/// An error from the `resolve` pass: an undefined name, a duplicate
+/// definition in the same scope, or an assignment to a function name.
+#[derive(Debug, Clone)]
+pub struct ResolveError {
+    pub message: String,
+    pub location: Location,
+}
+
+impl ResolveError {
+    pub fn undefined(name: &str, location: Location) -> ResolveError {
+        ResolveError {
+            message: format!("undefined name `{}`", name),
+            location,
+        }
+    }
+
+    pub fn duplicate(name: &str, location: Location) -> ResolveError {
+        ResolveError {
+            message: format!("`{}` is already defined in this scope", name),
+            location,
+        }
+    }
+
+    pub fn not_assignable(name: &str, location: Location) -> ResolveError {
+        ResolveError {
+            message: format!("`{}` is a function, not a variable", name),
+            location,
+        }
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.location)
+    }
+}
+
+/// An error from the `typeck` pass: a type mismatch between what was
+/// expected and what was found.
+#[derive(Debug, Clone)]
+pub struct TypeError {
+    pub message: String,
+    pub location: Location,
+}
+
+impl TypeError {
+    pub fn mismatch(expected: &crate::ty::Type, found: &crate::ty::Type, location: Location) -> TypeError {
+        TypeError {
+            message: format!("expected `{}`, found `{}`", expected, found),
+            location,
+        }
+    }
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.location)
+    }
+}
@@ -0,0 +1,8 @@
+/// Identifies a function definition, at any nesting depth, by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DefId(pub u32);
+
+/// Identifies a binding local to a function or block by index. Indices
+/// are unique across the whole program, not restarted per function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LocalId(pub u32);
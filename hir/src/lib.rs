@@ -0,0 +1,65 @@
+extern crate parser;
+
+pub mod error;
+pub mod ids;
+pub mod resolve;
+pub mod tree;
+pub mod ty;
+pub mod typeck;
+
+pub use error::{ResolveError, TypeError};
+pub use ids::{DefId, LocalId};
+pub use resolve::Resolver;
+pub use ty::Type;
+
+/// Lower a parsed program to HIR, running both the `resolve` and `typeck`
+/// passes. Returns the HIR even when errors are present so a caller (e.g.
+/// an IDE) can still inspect whatever was successfully lowered.
+pub fn lower(program: &parser::parse_tree::Program) -> (tree::Program, Vec<ResolveError>, Vec<TypeError>) {
+    let (mut hir, resolve_errors) = Resolver::new().lower_program(program);
+    let type_errors = typeck::typeck(&mut hir);
+    (hir, resolve_errors, type_errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lower_source(source: &str) -> (Vec<ResolveError>, Vec<TypeError>) {
+        let (program, diagnostics) = parser::parser::parse(source);
+        assert!(diagnostics.is_empty(), "unexpected parse errors: {:?}", diagnostics);
+        let (_hir, resolve_errors, type_errors) = lower(&program);
+        (resolve_errors, type_errors)
+    }
+
+    #[test]
+    fn undefined_name_is_a_resolve_error() {
+        let (resolve_errors, _) = lower_source("x;\n");
+        assert_eq!(resolve_errors.len(), 1);
+    }
+
+    #[test]
+    fn redeclaring_a_name_in_the_same_scope_is_a_resolve_error() {
+        let (resolve_errors, _) = lower_source("let x = 1;\nlet x = 2;\n");
+        assert_eq!(resolve_errors.len(), 1);
+    }
+
+    #[test]
+    fn shadowing_in_a_nested_scope_is_not_a_resolve_error() {
+        let (resolve_errors, _) = lower_source("let x = 1;\nif x { let x = 2; }\n");
+        assert!(resolve_errors.is_empty());
+    }
+
+    #[test]
+    fn mismatched_operand_types_are_a_type_error() {
+        let (_, type_errors) = lower_source("1 + true;\n");
+        assert_eq!(type_errors.len(), 1);
+    }
+
+    #[test]
+    fn assigning_to_a_function_name_reports_not_assignable_not_undefined() {
+        let (resolve_errors, _) = lower_source("fn f() { return 1; }\nf = 2;\n");
+        assert_eq!(resolve_errors.len(), 1);
+        assert!(resolve_errors[0].message.contains("not a variable"));
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use parser::parse_tree as pt;
+
+use crate::error::ResolveError;
+use crate::ids::{DefId, LocalId};
+use crate::tree;
+use crate::ty::Type;
+
+#[derive(Clone, Copy)]
+enum Binding {
+    Def(DefId),
+    Local(LocalId),
+}
+
+/// Walks a `parse_tree`, building scopes and resolving every identifier
+/// to a `DefId` or `LocalId`. Desugars compound assignment and implicit
+/// (braceless) blocks along the way.
+pub struct Resolver {
+    scopes: Vec<HashMap<String, Binding>>,
+    next_def: u32,
+    next_local: u32,
+    errors: Vec<ResolveError>,
+}
+
+impl Resolver {
+    pub fn new() -> Resolver {
+        Resolver {
+            scopes: vec![HashMap::new()],
+            next_def: 0,
+            next_local: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn lower_program(mut self, program: &pt::Program) -> (tree::Program, Vec<ResolveError>) {
+        let items = self.lower_stmts(&program.items);
+        (tree::Program { items }, self.errors)
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, name: &str, binding: Binding, location: parser::location::Location) {
+        let scope = self.scopes.last_mut().unwrap();
+        if scope.contains_key(name) {
+            self.errors.push(ResolveError::duplicate(name, location));
+        }
+        scope.insert(name.to_string(), binding);
+    }
+
+    fn lookup(&self, name: &str) -> Option<Binding> {
+        for scope in self.scopes.iter().rev() {
+            if let Some(binding) = scope.get(name) {
+                return Some(*binding);
+            }
+        }
+        None
+    }
+
+    fn fresh_local(&mut self) -> LocalId {
+        let id = LocalId(self.next_local);
+        self.next_local += 1;
+        id
+    }
+
+    fn fresh_def(&mut self) -> DefId {
+        let id = DefId(self.next_def);
+        self.next_def += 1;
+        id
+    }
+
+    fn lower_stmts(&mut self, stmts: &[pt::Stmt]) -> Vec<tree::Stmt> {
+        stmts.iter().map(|stmt| self.lower_stmt(stmt)).collect()
+    }
+
+    /// Normalize a `pt::Body` (a brace block or a single implicit
+    /// statement) into a plain `Vec<Stmt>`.
+    fn lower_body(&mut self, body: &pt::Body) -> Vec<tree::Stmt> {
+        self.push_scope();
+        let lowered = match body {
+            pt::Body::Block(stmts) => self.lower_stmts(stmts),
+            pt::Body::Single(stmt) => vec![self.lower_stmt(stmt)],
+        };
+        self.pop_scope();
+        lowered
+    }
+
+    fn lower_stmt(&mut self, stmt: &pt::Stmt) -> tree::Stmt {
+        match stmt {
+            pt::Stmt::Let { name, value, location } => {
+                let value = self.lower_expr(value);
+                let local = self.fresh_local();
+                self.declare(name, Binding::Local(local), *location);
+                tree::Stmt::Let { local, value }
+            }
+
+            pt::Stmt::Expr(expr) => tree::Stmt::Expr(self.lower_expr(expr)),
+
+            pt::Stmt::Return(expr) => {
+                tree::Stmt::Return(expr.as_ref().map(|e| self.lower_expr(e)))
+            }
+
+            pt::Stmt::FunctionDef { name, params, body, location } => {
+                let def = self.fresh_def();
+                self.declare(name, Binding::Def(def), *location);
+
+                self.push_scope();
+                let locals: Vec<LocalId> = params
+                    .iter()
+                    .map(|param| {
+                        let local = self.fresh_local();
+                        self.declare(&param.name, Binding::Local(local), *location);
+                        local
+                    })
+                    .collect();
+                let lowered_body = self.lower_stmts(body);
+                self.pop_scope();
+
+                tree::Stmt::FunctionDef {
+                    def,
+                    params: locals,
+                    body: lowered_body,
+                    ty: Type::Unknown,
+                }
+            }
+
+            pt::Stmt::If { cond, then_branch, else_branch, location } => {
+                let cond = Box::new(self.lower_expr(cond));
+                let then_block = self.lower_body(then_branch);
+                let else_block = match else_branch {
+                    Some(body) => self.lower_body(body),
+                    None => Vec::new(),
+                };
+                tree::Stmt::Expr(tree::Expr::If {
+                    cond,
+                    then_block,
+                    else_block,
+                    location: *location,
+                    ty: Type::Unknown,
+                })
+            }
+
+            // The parser already recorded a diagnostic for this region;
+            // lower it through unchanged rather than raising a second,
+            // redundant resolve error.
+            pt::Stmt::Error(location) => tree::Stmt::Expr(tree::Expr::Error(*location)),
+        }
+    }
+
+    fn lower_expr(&mut self, expr: &pt::Expr) -> tree::Expr {
+        match expr {
+            pt::Expr::IntLiteral(n, loc) => tree::Expr::IntLiteral(*n, *loc),
+            pt::Expr::FloatLiteral(n, loc) => tree::Expr::FloatLiteral(*n, *loc),
+            pt::Expr::BoolLiteral(b, loc) => tree::Expr::BoolLiteral(*b, *loc),
+            pt::Expr::StrLiteral(s, loc) => tree::Expr::StrLiteral(s.clone(), *loc),
+
+            pt::Expr::Ident(name, loc) => match self.lookup(name) {
+                Some(Binding::Local(local)) => tree::Expr::Local(local, *loc),
+                Some(Binding::Def(def)) => tree::Expr::Global(def, *loc),
+                None => {
+                    self.errors.push(ResolveError::undefined(name, *loc));
+                    tree::Expr::Local(LocalId(u32::MAX), *loc)
+                }
+            },
+
+            // Desugar `name op= value` into `name = name op value`, and a
+            // plain `name = value` into an `Assign` with no wrapping op.
+            pt::Expr::Assign { name, op, value, location } => {
+                let local = match self.lookup(name) {
+                    Some(Binding::Local(local)) => local,
+                    Some(Binding::Def(_)) => {
+                        self.errors
+                            .push(ResolveError::not_assignable(name, *location));
+                        LocalId(u32::MAX)
+                    }
+                    None => {
+                        self.errors
+                            .push(ResolveError::undefined(name, *location));
+                        LocalId(u32::MAX)
+                    }
+                };
+                let rhs = self.lower_expr(value);
+                let value = match op {
+                    Some(op) => Box::new(tree::Expr::Binary {
+                        op: *op,
+                        lhs: Box::new(tree::Expr::Local(local, *location)),
+                        rhs: Box::new(rhs),
+                        location: *location,
+                        ty: Type::Unknown,
+                    }),
+                    None => Box::new(rhs),
+                };
+                tree::Expr::Assign {
+                    target: local,
+                    value,
+                    location: *location,
+                }
+            }
+
+            pt::Expr::Binary { op, lhs, rhs, location } => tree::Expr::Binary {
+                op: *op,
+                lhs: Box::new(self.lower_expr(lhs)),
+                rhs: Box::new(self.lower_expr(rhs)),
+                location: *location,
+                ty: Type::Unknown,
+            },
+
+            pt::Expr::Call { callee, args, location } => tree::Expr::Call {
+                callee: Box::new(self.lower_expr(callee)),
+                args: args.iter().map(|arg| self.lower_expr(arg)).collect(),
+                location: *location,
+                ty: Type::Unknown,
+            },
+
+            pt::Expr::Block(stmts, location) => {
+                self.push_scope();
+                let lowered = self.lower_stmts(stmts);
+                self.pop_scope();
+                tree::Expr::Block(lowered, *location, Type::Unknown)
+            }
+
+            pt::Expr::Error(location) => tree::Expr::Error(*location),
+        }
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Resolver {
+        Resolver::new()
+    }
+}
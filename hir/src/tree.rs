@@ -0,0 +1,83 @@
+use parser::location::Location;
+pub use parser::parse_tree::BinOp;
+
+use crate::ids::{DefId, LocalId};
+use crate::ty::Type;
+
+/// A lowered program: every name reference is a resolved `DefId` or
+/// `LocalId`, compound assignment and implicit blocks have been
+/// desugared, and every `Expr` node carries its source `Location`.
+/// `Stmt` nodes carry no `Location` of their own.
+pub struct Program {
+    pub items: Vec<Stmt>,
+}
+
+pub enum Expr {
+    IntLiteral(i64, Location),
+    FloatLiteral(f64, Location),
+    BoolLiteral(bool, Location),
+    StrLiteral(String, Location),
+    Local(LocalId, Location),
+    Global(DefId, Location),
+    Assign {
+        target: LocalId,
+        value: Box<Expr>,
+        location: Location,
+    },
+    Binary {
+        op: BinOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+        location: Location,
+        ty: Type,
+    },
+    Call {
+        callee: Box<Expr>,
+        args: Vec<Expr>,
+        location: Location,
+        ty: Type,
+    },
+    If {
+        cond: Box<Expr>,
+        then_block: Vec<Stmt>,
+        else_block: Vec<Stmt>,
+        location: Location,
+        ty: Type,
+    },
+    Block(Vec<Stmt>, Location, Type),
+    /// A recovered parse error lowered straight through; `typeck` gives
+    /// it `Type::Unknown` rather than reporting a second error on top of
+    /// the parser's diagnostic.
+    Error(Location),
+}
+
+impl Expr {
+    pub fn location(&self) -> Location {
+        match self {
+            Expr::IntLiteral(_, loc)
+            | Expr::FloatLiteral(_, loc)
+            | Expr::BoolLiteral(_, loc)
+            | Expr::StrLiteral(_, loc)
+            | Expr::Local(_, loc)
+            | Expr::Global(_, loc)
+            | Expr::Assign { location: loc, .. }
+            | Expr::Binary { location: loc, .. }
+            | Expr::Call { location: loc, .. }
+            | Expr::If { location: loc, .. }
+            | Expr::Block(_, loc, _)
+            | Expr::Error(loc) => *loc,
+        }
+    }
+}
+
+pub enum Stmt {
+    Let { local: LocalId, value: Expr },
+    Expr(Expr),
+    Return(Option<Expr>),
+    FunctionDef {
+        def: DefId,
+        params: Vec<LocalId>,
+        body: Vec<Stmt>,
+        ty: Type,
+    },
+}
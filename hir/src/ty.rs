@@ -0,0 +1,35 @@
+/// A type as seen by `typeck`. `Unknown` marks a node that hasn't been
+/// annotated yet or whose type couldn't be determined after an error.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Int,
+    Float,
+    Bool,
+    Str,
+    Unit,
+    Function(Vec<Type>, Box<Type>),
+    Unknown,
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Int => write!(f, "int"),
+            Type::Float => write!(f, "float"),
+            Type::Bool => write!(f, "bool"),
+            Type::Str => write!(f, "str"),
+            Type::Unit => write!(f, "unit"),
+            Type::Function(params, ret) => {
+                write!(f, "fn(")?;
+                for (i, p) in params.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", p)?;
+                }
+                write!(f, ") -> {}", ret)
+            }
+            Type::Unknown => write!(f, "_"),
+        }
+    }
+}
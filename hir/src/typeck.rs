@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use parser::parse_tree::BinOp;
+
+use crate::error::TypeError;
+use crate::ids::{DefId, LocalId};
+use crate::tree::{Expr, Program, Stmt};
+use crate::ty::Type;
+
+/// Annotate every expression in `program` with its inferred `Type`,
+/// reporting a `TypeError` wherever two types are used together that
+/// don't agree.
+pub fn typeck(program: &mut Program) -> Vec<TypeError> {
+    let mut checker = Checker {
+        locals: HashMap::new(),
+        defs: HashMap::new(),
+        errors: Vec::new(),
+    };
+    checker.check_stmts(&mut program.items);
+    checker.errors
+}
+
+struct Checker {
+    locals: HashMap<LocalId, Type>,
+    defs: HashMap<DefId, Type>,
+    errors: Vec<TypeError>,
+}
+
+impl Checker {
+    fn check_stmts(&mut self, stmts: &mut [Stmt]) -> Type {
+        let mut last = Type::Unit;
+        for stmt in stmts {
+            last = self.check_stmt(stmt);
+        }
+        last
+    }
+
+    fn check_stmt(&mut self, stmt: &mut Stmt) -> Type {
+        match stmt {
+            Stmt::Let { local, value } => {
+                let ty = self.check_expr(value);
+                self.locals.insert(*local, ty);
+                Type::Unit
+            }
+            Stmt::Expr(expr) => self.check_expr(expr),
+            Stmt::Return(expr) => {
+                if let Some(expr) = expr {
+                    self.check_expr(expr);
+                }
+                Type::Unit
+            }
+            Stmt::FunctionDef { def, params, body, ty } => {
+                // Parameter types aren't annotated in source yet, so
+                // assume `Unknown` for each and let call sites flow
+                // through without a mismatch.
+                for param in params.iter() {
+                    self.locals.insert(*param, Type::Unknown);
+                }
+                let ret = self.check_stmts(body);
+                let fn_ty = Type::Function(vec![Type::Unknown; params.len()], Box::new(ret));
+                self.defs.insert(*def, fn_ty.clone());
+                *ty = fn_ty;
+                Type::Unit
+            }
+        }
+    }
+
+    fn check_expr(&mut self, expr: &mut Expr) -> Type {
+        match expr {
+            Expr::IntLiteral(_, _) => Type::Int,
+            Expr::FloatLiteral(_, _) => Type::Float,
+            Expr::BoolLiteral(_, _) => Type::Bool,
+            Expr::StrLiteral(_, _) => Type::Str,
+
+            Expr::Local(local, _) => self.locals.get(local).cloned().unwrap_or(Type::Unknown),
+            Expr::Global(def, _) => self.defs.get(def).cloned().unwrap_or(Type::Unknown),
+
+            Expr::Assign { target, value, .. } => {
+                let value_ty = self.check_expr(value);
+                self.locals.insert(*target, value_ty);
+                Type::Unit
+            }
+
+            Expr::Binary { op, lhs, rhs, location, ty } => {
+                let lhs_ty = self.check_expr(lhs);
+                let rhs_ty = self.check_expr(rhs);
+                *ty = binop_result(*op, &lhs_ty, &rhs_ty, *location, &mut self.errors);
+                ty.clone()
+            }
+
+            Expr::Call { callee, args, location, ty } => {
+                let callee_ty = self.check_expr(callee);
+                for arg in args.iter_mut() {
+                    self.check_expr(arg);
+                }
+                *ty = match callee_ty {
+                    Type::Function(_, ret) => *ret,
+                    Type::Unknown => Type::Unknown,
+                    other => {
+                        self.errors.push(TypeError::mismatch(
+                            &Type::Function(vec![], Box::new(Type::Unknown)),
+                            &other,
+                            *location,
+                        ));
+                        Type::Unknown
+                    }
+                };
+                ty.clone()
+            }
+
+            Expr::If { cond, then_block, else_block, location, ty } => {
+                let cond_ty = self.check_expr(cond);
+                if cond_ty != Type::Bool && cond_ty != Type::Unknown {
+                    self.errors
+                        .push(TypeError::mismatch(&Type::Bool, &cond_ty, *location));
+                }
+                let then_ty = self.check_stmts(then_block);
+                let else_ty = self.check_stmts(else_block);
+                *ty = if then_ty == else_ty { then_ty } else { Type::Unknown };
+                ty.clone()
+            }
+
+            Expr::Block(stmts, _, ty) => {
+                *ty = self.check_stmts(stmts);
+                ty.clone()
+            }
+
+            // Already diagnosed during parsing or resolve; don't pile on.
+            Expr::Error(_) => Type::Unknown,
+        }
+    }
+}
+
+fn binop_result(
+    op: BinOp,
+    lhs: &Type,
+    rhs: &Type,
+    location: parser::location::Location,
+    errors: &mut Vec<TypeError>,
+) -> Type {
+    use BinOp::*;
+    match (op, lhs, rhs) {
+        (_, Type::Unknown, _) | (_, _, Type::Unknown) => Type::Unknown,
+        (Add | Sub | Mul | Div, Type::Int, Type::Int) => Type::Int,
+        (Add | Sub | Mul | Div, Type::Float, Type::Float) => Type::Float,
+        (Add, Type::Str, Type::Str) => Type::Str,
+        (Eq | Ne | Lt | Le | Gt | Ge, a, b) => {
+            if a != b {
+                errors.push(TypeError::mismatch(a, b, location));
+            }
+            Type::Bool
+        }
+        (And | Or, Type::Bool, Type::Bool) => Type::Bool,
+        (_, a, b) => {
+            errors.push(TypeError::mismatch(a, b, location));
+            Type::Unknown
+        }
+    }
+}
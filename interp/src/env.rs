@@ -0,0 +1,71 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::value::Value;
+
+struct Scope {
+    vars: HashMap<String, Value>,
+    parent: Option<Env>,
+}
+
+/// A lexical environment: a chain of scopes, each a `HashMap` of bindings
+/// with a pointer to its parent. Cloning an `Env` shares the same chain,
+/// which is what lets a closure capture the scope it was defined in.
+#[derive(Clone)]
+pub struct Env(Rc<RefCell<Scope>>);
+
+impl Env {
+    pub fn new() -> Env {
+        Env(Rc::new(RefCell::new(Scope {
+            vars: HashMap::new(),
+            parent: None,
+        })))
+    }
+
+    /// Push a new child scope on top of this one.
+    pub fn child(&self) -> Env {
+        Env(Rc::new(RefCell::new(Scope {
+            vars: HashMap::new(),
+            parent: Some(self.clone()),
+        })))
+    }
+
+    /// Bind `name` in this scope, shadowing any binding of the same name
+    /// in an enclosing scope.
+    pub fn define(&self, name: impl Into<String>, value: Value) {
+        self.0.borrow_mut().vars.insert(name.into(), value);
+    }
+
+    /// Look up `name`, walking outward through parent scopes.
+    pub fn get(&self, name: &str) -> Option<Value> {
+        let scope = self.0.borrow();
+        if let Some(value) = scope.vars.get(name) {
+            return Some(value.clone());
+        }
+        match &scope.parent {
+            Some(parent) => parent.get(name),
+            None => None,
+        }
+    }
+
+    /// Assign to an existing binding of `name`, walking outward through
+    /// parent scopes. Returns `false` if `name` is not bound anywhere.
+    pub fn assign(&self, name: &str, value: Value) -> bool {
+        let mut scope = self.0.borrow_mut();
+        if let Some(slot) = scope.vars.get_mut(name) {
+            *slot = value;
+            return true;
+        }
+        match &scope.parent {
+            Some(parent) => parent.assign(name, value),
+            None => false,
+        }
+    }
+}
+
+impl Default for Env {
+    fn default() -> Env {
+        Env::new()
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt;
+
+use parser::location::Location;
+
+/// Distinguishes why a `RuntimeError` was raised, so a caller like the
+/// sandbox layer can react to a specific cause without matching on the
+/// message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeErrorKind {
+    General,
+    BudgetExceeded,
+    IoDenied,
+}
+
+/// An error raised while evaluating a parsed program.
+#[derive(Debug, Clone)]
+pub struct RuntimeError {
+    pub kind: RuntimeErrorKind,
+    pub message: String,
+    pub location: Location,
+}
+
+impl RuntimeError {
+    pub fn new(message: impl Into<String>, location: Location) -> RuntimeError {
+        RuntimeError {
+            kind: RuntimeErrorKind::General,
+            message: message.into(),
+            location,
+        }
+    }
+
+    pub fn type_mismatch(expected: &str, found: &str, location: Location) -> RuntimeError {
+        RuntimeError::new(
+            format!("expected a value of type `{}`, found `{}`", expected, found),
+            location,
+        )
+    }
+
+    pub fn undefined(name: &str, location: Location) -> RuntimeError {
+        RuntimeError::new(format!("undefined name `{}`", name), location)
+    }
+
+    pub fn budget_exceeded(location: Location) -> RuntimeError {
+        RuntimeError {
+            kind: RuntimeErrorKind::BudgetExceeded,
+            message: "instruction budget exceeded".to_string(),
+            location,
+        }
+    }
+
+    pub fn io_denied(op: &str, location: Location) -> RuntimeError {
+        RuntimeError {
+            kind: RuntimeErrorKind::IoDenied,
+            message: format!("operation `{}` is not permitted here", op),
+            location,
+        }
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.location)
+    }
+}
+
+impl std::error::Error for RuntimeError {}
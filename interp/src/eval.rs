@@ -0,0 +1,565 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Instant;
+
+use parser::location::Location;
+use parser::parse_tree::{BinOp, Body, Expr, Stmt};
+
+use crate::env::Env;
+use crate::error::RuntimeError;
+use crate::value::{Closure, Value};
+
+/// Control flow unwound out of `exec_stmt` by a `return`.
+pub enum Flow {
+    Normal,
+    Return(Value),
+}
+
+/// Called before a builtin runs, so a sandbox layer can allow or deny it.
+/// Receives the builtin's name, its already-evaluated arguments, and the
+/// call site's location.
+pub type IoHook = Rc<dyn Fn(&str, &[Value], Location) -> Result<(), RuntimeError>>;
+
+/// Caps native recursion depth so a short recursive Charj function hits a
+/// clean `RuntimeError` well before it could overflow the host stack,
+/// independent of (and much lower than) any instruction budget. Kept low
+/// enough that even an unoptimized debug build, whose `eval_expr`/
+/// `exec_stmt` frames are far bigger than in release, stays well inside
+/// a default-sized thread stack.
+const MAX_CALL_DEPTH: u32 = 128;
+
+/// Walks a `parse_tree` and evaluates it against an `Env`. `budget` and
+/// `deadline` are `None` by default (unbounded execution); the `sandbox`
+/// crate sets them to cap a run.
+pub struct Interpreter {
+    pub globals: Env,
+    pub budget: Cell<Option<u64>>,
+    pub deadline: Cell<Option<Instant>>,
+    pub io_hook: Option<IoHook>,
+    call_depth: Cell<u32>,
+}
+
+impl Interpreter {
+    pub fn new() -> Interpreter {
+        let globals = Env::new();
+        globals.define("read_file", Value::Builtin(Rc::from("read_file")));
+        Interpreter {
+            globals,
+            budget: Cell::new(None),
+            deadline: Cell::new(None),
+            io_hook: None,
+            call_depth: Cell::new(0),
+        }
+    }
+
+    /// Decrement the instruction budget (if any) and check the wall-clock
+    /// deadline (if any), failing the step that triggered either.
+    fn tick(&self, location: Location) -> Result<(), RuntimeError> {
+        if let Some(remaining) = self.budget.get() {
+            if remaining == 0 {
+                return Err(RuntimeError::budget_exceeded(location));
+            }
+            self.budget.set(Some(remaining - 1));
+        }
+        if let Some(deadline) = self.deadline.get() {
+            if Instant::now() >= deadline {
+                return Err(RuntimeError::budget_exceeded(location));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn eval_expr(&mut self, node: &Expr, env: &Env) -> Result<Value, RuntimeError> {
+        self.tick(expr_location(node))?;
+        match node {
+            Expr::IntLiteral(n, _) => Ok(Value::Int(*n)),
+            Expr::FloatLiteral(n, _) => Ok(Value::Float(*n)),
+            Expr::BoolLiteral(b, _) => Ok(Value::Bool(*b)),
+            Expr::StrLiteral(s, _) => Ok(Value::Str(s.clone())),
+
+            Expr::Ident(name, location) => env
+                .get(name)
+                .ok_or_else(|| RuntimeError::undefined(name, *location)),
+
+            Expr::Binary { op, lhs, rhs, location } => {
+                let lhs = self.eval_expr(lhs, env)?;
+                let rhs = self.eval_expr(rhs, env)?;
+                eval_binop(*op, lhs, rhs, *location)
+            }
+
+            Expr::Block(stmts, location) => {
+                let scope = env.child();
+                let mut result = Value::Unit;
+                for stmt in stmts {
+                    result = if let Stmt::Expr(expr) = stmt {
+                        self.eval_expr(expr, &scope)?
+                    } else {
+                        match self.exec_stmt(stmt, &scope)? {
+                            Flow::Normal => Value::Unit,
+                            Flow::Return(_) => {
+                                return Err(RuntimeError::new(
+                                    "`return` outside of a function",
+                                    *location,
+                                ));
+                            }
+                        }
+                    };
+                }
+                Ok(result)
+            }
+
+            Expr::Call { callee, args, location } => {
+                let callee = self.eval_expr(callee, env)?;
+                match callee {
+                    Value::Function(closure) => self.call(&closure, args, env, *location),
+                    Value::Builtin(name) => self.call_builtin(&name, args, env, *location),
+                    other => Err(RuntimeError::type_mismatch(
+                        "function",
+                        other.type_name(),
+                        *location,
+                    )),
+                }
+            }
+
+            Expr::Assign { name, op, value, location } => {
+                let rhs = self.eval_expr(value, env)?;
+                let new_value = match op {
+                    Some(op) => {
+                        let current = env
+                            .get(name)
+                            .ok_or_else(|| RuntimeError::undefined(name, *location))?;
+                        eval_binop(*op, current, rhs, *location)?
+                    }
+                    None => rhs,
+                };
+                if !env.assign(name, new_value) {
+                    return Err(RuntimeError::undefined(name, *location));
+                }
+                Ok(Value::Unit)
+            }
+
+            Expr::Error(location) => {
+                Err(RuntimeError::new("cannot evaluate a syntax error", *location))
+            }
+        }
+    }
+
+    pub fn exec_stmt(&mut self, node: &Stmt, env: &Env) -> Result<Flow, RuntimeError> {
+        if let Some(location) = stmt_location(node) {
+            self.tick(location)?;
+        }
+        match node {
+            Stmt::Let { name, value, .. } => {
+                let value = self.eval_expr(value, env)?;
+                env.define(name.clone(), value);
+                Ok(Flow::Normal)
+            }
+
+            Stmt::Expr(expr) => {
+                self.eval_expr(expr, env)?;
+                Ok(Flow::Normal)
+            }
+
+            Stmt::Return(expr) => {
+                let value = match expr {
+                    Some(expr) => self.eval_expr(expr, env)?,
+                    None => Value::Unit,
+                };
+                Ok(Flow::Return(value))
+            }
+
+            Stmt::FunctionDef { name, params, body, .. } => {
+                let closure = Value::Function(Rc::new(Closure {
+                    name: Some(name.clone()),
+                    params: params.clone(),
+                    body: body.clone(),
+                    env: env.clone(),
+                }));
+                env.define(name.clone(), closure);
+                Ok(Flow::Normal)
+            }
+
+            Stmt::If { cond, then_branch, else_branch, .. } => {
+                let branch = if self.eval_expr(cond, env)?.is_truthy() {
+                    Some(then_branch)
+                } else {
+                    else_branch.as_ref()
+                };
+                let Some(body) = branch else {
+                    return Ok(Flow::Normal);
+                };
+
+                let scope = env.child();
+                let owned_single;
+                let stmts: &[Stmt] = match body {
+                    Body::Block(stmts) => stmts,
+                    Body::Single(stmt) => {
+                        owned_single = [stmt.as_ref().clone()];
+                        &owned_single
+                    }
+                };
+                for stmt in stmts {
+                    if let Flow::Return(value) = self.exec_stmt(stmt, &scope)? {
+                        return Ok(Flow::Return(value));
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+
+            Stmt::Error(location) => {
+                Err(RuntimeError::new("cannot execute a syntax error", *location))
+            }
+        }
+    }
+
+    /// Run a function body in a fresh scope whose parent is the closure's
+    /// captured environment, with parameters bound positionally.
+    fn call(
+        &mut self,
+        closure: &Closure,
+        args: &[Expr],
+        caller_env: &Env,
+        location: Location,
+    ) -> Result<Value, RuntimeError> {
+        if args.len() != closure.params.len() {
+            return Err(RuntimeError::new(
+                format!(
+                    "expected {} argument(s), found {}",
+                    closure.params.len(),
+                    args.len()
+                ),
+                location,
+            ));
+        }
+
+        let depth = self.call_depth.get() + 1;
+        if depth > MAX_CALL_DEPTH {
+            return Err(RuntimeError::new(
+                "call stack depth exceeded (possible infinite recursion)",
+                location,
+            ));
+        }
+        self.call_depth.set(depth);
+        let result = self.call_body(closure, args, caller_env);
+        self.call_depth.set(depth - 1);
+        result
+    }
+
+    /// The actual work of `call`, split out so `call` only has to deal with
+    /// the depth counter (incremented/decremented around this) and the
+    /// arity check.
+    fn call_body(
+        &mut self,
+        closure: &Closure,
+        args: &[Expr],
+        caller_env: &Env,
+    ) -> Result<Value, RuntimeError> {
+        let call_scope = closure.env.child();
+        for (param, arg) in closure.params.iter().zip(args) {
+            let value = self.eval_expr(arg, caller_env)?;
+            call_scope.define(param.name.clone(), value);
+        }
+
+        for stmt in &closure.body {
+            if let Flow::Return(value) = self.exec_stmt(stmt, &call_scope)? {
+                return Ok(value);
+            }
+        }
+        Ok(Value::Unit)
+    }
+
+    /// Dispatch a call to a native function, consulting `io_hook` first so
+    /// a sandboxed run can deny it before any I/O happens.
+    fn call_builtin(
+        &mut self,
+        name: &str,
+        args: &[Expr],
+        env: &Env,
+        location: Location,
+    ) -> Result<Value, RuntimeError> {
+        let values = args
+            .iter()
+            .map(|arg| self.eval_expr(arg, env))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(hook) = self.io_hook.clone() {
+            hook(name, &values, location)?;
+        }
+
+        match name {
+            "read_file" => {
+                let path = match values.first() {
+                    Some(Value::Str(path)) => path.clone(),
+                    Some(other) => {
+                        return Err(RuntimeError::type_mismatch("str", other.type_name(), location))
+                    }
+                    None => return Err(RuntimeError::new("read_file expects 1 argument", location)),
+                };
+                std::fs::read_to_string(&path)
+                    .map(Value::Str)
+                    .map_err(|err| RuntimeError::new(format!("{}: {}", path, err), location))
+            }
+            other => Err(RuntimeError::new(format!("undefined name `{}`", other), location)),
+        }
+    }
+}
+
+impl Default for Interpreter {
+    fn default() -> Interpreter {
+        Interpreter::new()
+    }
+}
+
+fn expr_location(expr: &Expr) -> Location {
+    match expr {
+        Expr::IntLiteral(_, loc)
+        | Expr::FloatLiteral(_, loc)
+        | Expr::BoolLiteral(_, loc)
+        | Expr::StrLiteral(_, loc)
+        | Expr::Ident(_, loc)
+        | Expr::Block(_, loc)
+        | Expr::Error(loc) => *loc,
+        Expr::Assign { location, .. }
+        | Expr::Binary { location, .. }
+        | Expr::Call { location, .. } => *location,
+    }
+}
+
+fn stmt_location(stmt: &Stmt) -> Option<Location> {
+    match stmt {
+        Stmt::Let { value, .. } => Some(expr_location(value)),
+        Stmt::Expr(expr) => Some(expr_location(expr)),
+        Stmt::Return(Some(expr)) => Some(expr_location(expr)),
+        Stmt::Return(None) => None,
+        Stmt::FunctionDef { .. } => None,
+        Stmt::If { location, .. } => Some(*location),
+        Stmt::Error(location) => Some(*location),
+    }
+}
+
+fn eval_binop(
+    op: BinOp,
+    lhs: Value,
+    rhs: Value,
+    location: Location,
+) -> Result<Value, RuntimeError> {
+    use BinOp::*;
+    match (op, lhs, rhs) {
+        (Add, Value::Int(a), Value::Int(b)) => Ok(Value::Int(a + b)),
+        (Add, Value::Float(a), Value::Float(b)) => Ok(Value::Float(a + b)),
+        (Add, Value::Str(a), Value::Str(b)) => Ok(Value::Str(a + &b)),
+        (Sub, Value::Int(a), Value::Int(b)) => Ok(Value::Int(a - b)),
+        (Sub, Value::Float(a), Value::Float(b)) => Ok(Value::Float(a - b)),
+        (Mul, Value::Int(a), Value::Int(b)) => Ok(Value::Int(a * b)),
+        (Mul, Value::Float(a), Value::Float(b)) => Ok(Value::Float(a * b)),
+        (Div, Value::Int(_), Value::Int(0)) => {
+            Err(RuntimeError::new("division by zero", location))
+        }
+        (Div, Value::Int(a), Value::Int(b)) => match a.checked_div(b) {
+            Some(result) => Ok(Value::Int(result)),
+            None => Err(RuntimeError::new("integer overflow in division", location)),
+        },
+        (Div, Value::Float(a), Value::Float(b)) => Ok(Value::Float(a / b)),
+
+        (Eq, a, b) => Ok(Value::Bool(values_eq(&a, &b))),
+        (Ne, a, b) => Ok(Value::Bool(!values_eq(&a, &b))),
+        (Lt, Value::Int(a), Value::Int(b)) => Ok(Value::Bool(a < b)),
+        (Le, Value::Int(a), Value::Int(b)) => Ok(Value::Bool(a <= b)),
+        (Gt, Value::Int(a), Value::Int(b)) => Ok(Value::Bool(a > b)),
+        (Ge, Value::Int(a), Value::Int(b)) => Ok(Value::Bool(a >= b)),
+
+        (And, a, b) => Ok(Value::Bool(a.is_truthy() && b.is_truthy())),
+        (Or, a, b) => Ok(Value::Bool(a.is_truthy() || b.is_truthy())),
+
+        (op, a, b) => Err(RuntimeError::new(
+            format!(
+                "cannot apply `{:?}` to `{}` and `{}`",
+                op,
+                a.type_name(),
+                b.type_name()
+            ),
+            location,
+        )),
+    }
+}
+
+fn values_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => a == b,
+        (Value::Float(a), Value::Float(b)) => a == b,
+        (Value::Bool(a), Value::Bool(b)) => a == b,
+        (Value::Str(a), Value::Str(b)) => a == b,
+        (Value::Unit, Value::Unit) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parser::parse_tree::Param;
+
+    fn loc() -> Location {
+        Location::new(1, 0, 0)
+    }
+
+    #[test]
+    fn let_in_a_block_shadows_without_leaking_out() {
+        let mut interp = Interpreter::new();
+        let env = interp.globals.clone();
+        env.define("x", Value::Int(1));
+
+        let block = Expr::Block(
+            vec![
+                Stmt::Let {
+                    name: "x".to_string(),
+                    value: Expr::IntLiteral(2, loc()),
+                    location: loc(),
+                },
+                Stmt::Expr(Expr::Ident("x".to_string(), loc())),
+            ],
+            loc(),
+        );
+
+        let result = interp.eval_expr(&block, &env).unwrap();
+        assert!(matches!(result, Value::Int(2)));
+        assert!(matches!(env.get("x"), Some(Value::Int(1))));
+    }
+
+    #[test]
+    fn block_does_not_double_evaluate_expr_statements() {
+        let mut interp = Interpreter::new();
+        let env = interp.globals.clone();
+        env.define("n", Value::Int(0));
+
+        // { n += 1; n }
+        let block = Expr::Block(
+            vec![
+                Stmt::Expr(Expr::Assign {
+                    name: "n".to_string(),
+                    op: Some(BinOp::Add),
+                    value: Box::new(Expr::IntLiteral(1, loc())),
+                    location: loc(),
+                }),
+                Stmt::Expr(Expr::Ident("n".to_string(), loc())),
+            ],
+            loc(),
+        );
+
+        interp.eval_expr(&block, &env).unwrap();
+        assert!(matches!(env.get("n"), Some(Value::Int(1))));
+    }
+
+    #[test]
+    fn recursive_function_can_call_itself() {
+        let mut interp = Interpreter::new();
+        let env = interp.globals.clone();
+
+        // fn countdown(n) { if n != 0 { return countdown(n - 1); } return n; }
+        let body = vec![
+            Stmt::If {
+                cond: Expr::Binary {
+                    op: BinOp::Ne,
+                    lhs: Box::new(Expr::Ident("n".to_string(), loc())),
+                    rhs: Box::new(Expr::IntLiteral(0, loc())),
+                    location: loc(),
+                },
+                then_branch: Body::Single(Box::new(Stmt::Return(Some(Expr::Call {
+                    callee: Box::new(Expr::Ident("countdown".to_string(), loc())),
+                    args: vec![Expr::Binary {
+                        op: BinOp::Sub,
+                        lhs: Box::new(Expr::Ident("n".to_string(), loc())),
+                        rhs: Box::new(Expr::IntLiteral(1, loc())),
+                        location: loc(),
+                    }],
+                    location: loc(),
+                })))),
+                else_branch: None,
+                location: loc(),
+            },
+            Stmt::Return(Some(Expr::Ident("n".to_string(), loc()))),
+        ];
+
+        interp
+            .exec_stmt(
+                &Stmt::FunctionDef {
+                    name: "countdown".to_string(),
+                    params: vec![Param { name: "n".to_string() }],
+                    body,
+                    location: loc(),
+                },
+                &env,
+            )
+            .unwrap();
+
+        let call = Expr::Call {
+            callee: Box::new(Expr::Ident("countdown".to_string(), loc())),
+            args: vec![Expr::IntLiteral(3, loc())],
+            location: loc(),
+        };
+
+        let result = interp.eval_expr(&call, &env).unwrap();
+        assert!(matches!(result, Value::Int(0)));
+    }
+
+    #[test]
+    fn division_by_zero_is_a_runtime_error_not_a_panic() {
+        let mut interp = Interpreter::new();
+        let env = interp.globals.clone();
+        let expr = Expr::Binary {
+            op: BinOp::Div,
+            lhs: Box::new(Expr::IntLiteral(1, loc())),
+            rhs: Box::new(Expr::IntLiteral(0, loc())),
+            location: loc(),
+        };
+
+        assert!(interp.eval_expr(&expr, &env).is_err());
+    }
+
+    #[test]
+    fn min_int_divided_by_negative_one_is_an_error_not_a_panic() {
+        let mut interp = Interpreter::new();
+        let env = interp.globals.clone();
+        let expr = Expr::Binary {
+            op: BinOp::Div,
+            lhs: Box::new(Expr::IntLiteral(i64::MIN, loc())),
+            rhs: Box::new(Expr::IntLiteral(-1, loc())),
+            location: loc(),
+        };
+
+        assert!(interp.eval_expr(&expr, &env).is_err());
+    }
+
+    #[test]
+    fn unbounded_recursion_hits_the_call_depth_cap_instead_of_overflowing_the_stack() {
+        let mut interp = Interpreter::new();
+        let env = interp.globals.clone();
+
+        // fn loop_forever() { return loop_forever(); }
+        let body = vec![Stmt::Return(Some(Expr::Call {
+            callee: Box::new(Expr::Ident("loop_forever".to_string(), loc())),
+            args: vec![],
+            location: loc(),
+        }))];
+
+        interp
+            .exec_stmt(
+                &Stmt::FunctionDef {
+                    name: "loop_forever".to_string(),
+                    params: vec![],
+                    body,
+                    location: loc(),
+                },
+                &env,
+            )
+            .unwrap();
+
+        let call = Expr::Call {
+            callee: Box::new(Expr::Ident("loop_forever".to_string(), loc())),
+            args: vec![],
+            location: loc(),
+        };
+
+        assert!(interp.eval_expr(&call, &env).is_err());
+    }
+}
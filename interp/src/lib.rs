@@ -0,0 +1,11 @@
+extern crate parser;
+
+pub mod env;
+pub mod error;
+pub mod eval;
+pub mod value;
+
+pub use env::Env;
+pub use error::{RuntimeError, RuntimeErrorKind};
+pub use eval::{Interpreter, IoHook};
+pub use value::Value;
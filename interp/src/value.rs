@@ -0,0 +1,73 @@
+use std::fmt;
+use std::rc::Rc;
+
+use parser::parse_tree::{Param, Stmt};
+
+use crate::env::Env;
+
+/// A runtime value produced by the interpreter.
+#[derive(Clone)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    Function(Rc<Closure>),
+    /// A native function, looked up by name at call time. Used for
+    /// operations (like file I/O) that a `sandbox::Profile` can gate.
+    Builtin(Rc<str>),
+    Unit,
+}
+
+/// A function value together with the environment it closed over.
+pub struct Closure {
+    pub name: Option<String>,
+    pub params: Vec<Param>,
+    pub body: Vec<Stmt>,
+    pub env: Env,
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Int(_) => "int",
+            Value::Float(_) => "float",
+            Value::Bool(_) => "bool",
+            Value::Str(_) => "str",
+            Value::Function(_) => "function",
+            Value::Builtin(_) => "function",
+            Value::Unit => "unit",
+        }
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(b) => *b,
+            Value::Unit => false,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Float(n) => write!(f, "{}", n),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Str(s) => write!(f, "{}", s),
+            Value::Function(c) => match &c.name {
+                Some(name) => write!(f, "<function {}>", name),
+                None => write!(f, "<function>"),
+            },
+            Value::Builtin(name) => write!(f, "<builtin {}>", name),
+            Value::Unit => write!(f, "()"),
+        }
+    }
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::location::Location;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One parse problem, with enough information for an editor to underline
+/// the offending span and show a message.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub location: Location,
+    pub message: String,
+    pub severity: Severity,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>, location: Location) -> Diagnostic {
+        Diagnostic {
+            location,
+            message: message.into(),
+            severity: Severity::Error,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>, location: Location) -> Diagnostic {
+        Diagnostic {
+            location,
+            message: message.into(),
+            severity: Severity::Warning,
+        }
+    }
+}
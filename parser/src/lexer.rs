@@ -0,0 +1,228 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::location::Location;
+use crate::token::Token;
+
+#[derive(Debug, Clone)]
+pub struct LexError {
+    pub message: String,
+    pub location: Location,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.location)
+    }
+}
+
+impl std::error::Error for LexError {}
+
+/// A hand-written lexer implementing LALRPOP's external-lexer protocol:
+/// an iterator of `(start, token, end)` triples (or a `LexError`).
+pub struct Lexer<'input> {
+    source: &'input str,
+    chars: Peekable<CharIndices<'input>>,
+    line: usize,
+    line_start: usize,
+}
+
+impl<'input> Lexer<'input> {
+    pub fn new(source: &'input str) -> Lexer<'input> {
+        Lexer {
+            source,
+            chars: source.char_indices().peekable(),
+            line: 1,
+            line_start: 0,
+        }
+    }
+
+    fn location(&self, offset: usize) -> Location {
+        Location::new(self.line, offset.saturating_sub(self.line_start), offset)
+    }
+
+    fn bump(&mut self) -> Option<(usize, char)> {
+        let next = self.chars.next();
+        if let Some((offset, '\n')) = next {
+            self.line += 1;
+            self.line_start = offset + 1;
+        }
+        next
+    }
+
+    fn bump_if(&mut self, expected: char) -> bool {
+        if let Some(&(_, c)) = self.chars.peek() {
+            if c == expected {
+                self.bump();
+                return true;
+            }
+        }
+        false
+    }
+
+    fn current_offset(&mut self) -> usize {
+        self.chars.peek().map(|&(o, _)| o).unwrap_or(self.source.len())
+    }
+}
+
+impl<'input> Iterator for Lexer<'input> {
+    type Item = Result<(Location, Token, Location), LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let &(start, ch) = self.chars.peek()?;
+            if ch.is_whitespace() {
+                self.bump();
+                continue;
+            }
+            let start_loc = self.location(start);
+
+            if ch.is_ascii_digit() {
+                let mut end = start;
+                let mut is_float = false;
+                while let Some(&(offset, c)) = self.chars.peek() {
+                    if c.is_ascii_digit() {
+                        end = offset + c.len_utf8();
+                        self.bump();
+                    } else if c == '.' && !is_float {
+                        is_float = true;
+                        end = offset + c.len_utf8();
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                let text = &self.source[start..end];
+                let token = if is_float {
+                    Token::Float(text.parse().unwrap())
+                } else {
+                    match text.parse() {
+                        Ok(n) => Token::Int(n),
+                        Err(_) => {
+                            return Some(Err(LexError {
+                                message: format!("integer literal `{}` out of range", text),
+                                location: start_loc,
+                            }))
+                        }
+                    }
+                };
+                let end_offset = self.current_offset();
+                let end_loc = self.location(end_offset);
+                return Some(Ok((start_loc, token, end_loc)));
+            }
+
+            if ch.is_alphabetic() || ch == '_' {
+                let mut end = start;
+                while let Some(&(offset, c)) = self.chars.peek() {
+                    if c.is_alphanumeric() || c == '_' {
+                        end = offset + c.len_utf8();
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                let token = match &self.source[start..end] {
+                    "true" => Token::True,
+                    "false" => Token::False,
+                    "let" => Token::Let,
+                    "fn" => Token::Fn,
+                    "return" => Token::Return,
+                    "if" => Token::If,
+                    "else" => Token::Else,
+                    ident => Token::Ident(ident.to_string()),
+                };
+                let end_offset = self.current_offset();
+                let end_loc = self.location(end_offset);
+                return Some(Ok((start_loc, token, end_loc)));
+            }
+
+            if ch == '"' {
+                self.bump();
+                let mut value = String::new();
+                loop {
+                    match self.bump() {
+                        Some((_, '"')) => break,
+                        Some((_, c)) => value.push(c),
+                        None => {
+                            return Some(Err(LexError {
+                                message: "unterminated string literal".to_string(),
+                                location: start_loc,
+                            }))
+                        }
+                    }
+                }
+                let end_offset = self.current_offset();
+                let end_loc = self.location(end_offset);
+                return Some(Ok((start_loc, Token::Str(value), end_loc)));
+            }
+
+            let token = match ch {
+                '(' => { self.bump(); Token::LParen }
+                ')' => { self.bump(); Token::RParen }
+                '{' => { self.bump(); Token::LBrace }
+                '}' => { self.bump(); Token::RBrace }
+                '[' => { self.bump(); Token::LBracket }
+                ']' => { self.bump(); Token::RBracket }
+                ',' => { self.bump(); Token::Comma }
+                ';' => { self.bump(); Token::Semi }
+                '*' => { self.bump(); Token::Star }
+                '/' => { self.bump(); Token::Slash }
+                '=' => { self.bump(); if self.bump_if('=') { Token::EqEq } else { Token::Assign } }
+                '<' => { self.bump(); if self.bump_if('=') { Token::Le } else { Token::Lt } }
+                '>' => { self.bump(); if self.bump_if('=') { Token::Ge } else { Token::Gt } }
+                '+' => { self.bump(); if self.bump_if('=') { Token::PlusAssign } else { Token::Plus } }
+                '-' => { self.bump(); if self.bump_if('=') { Token::MinusAssign } else { Token::Minus } }
+                '!' => {
+                    self.bump();
+                    if self.bump_if('=') {
+                        Token::Ne
+                    } else {
+                        return Some(Err(LexError {
+                            message: "unexpected character `!`".to_string(),
+                            location: start_loc,
+                        }));
+                    }
+                }
+                '&' => {
+                    self.bump();
+                    if self.bump_if('&') {
+                        Token::AndAnd
+                    } else {
+                        return Some(Err(LexError {
+                            message: "unexpected character `&`".to_string(),
+                            location: start_loc,
+                        }));
+                    }
+                }
+                '|' => {
+                    self.bump();
+                    if self.bump_if('|') {
+                        Token::OrOr
+                    } else {
+                        return Some(Err(LexError {
+                            message: "unexpected character `|`".to_string(),
+                            location: start_loc,
+                        }));
+                    }
+                }
+                other => {
+                    self.bump();
+                    return Some(Err(LexError {
+                        message: format!("unexpected character `{}`", other),
+                        location: start_loc,
+                    }));
+                }
+            };
+            let end_offset = self.current_offset();
+                let end_loc = self.location(end_offset);
+            return Some(Ok((start_loc, token, end_loc)));
+        }
+    }
+}
+
+/// Lex all of `source` into a flat token list. Used by the REPL to tell
+/// whether a parse failure was caused by input truncated mid-token-stream.
+pub fn lex(source: &str) -> Result<Vec<Token>, LexError> {
+    Lexer::new(source).map(|r| r.map(|(_, token, _)| token)).collect()
+}
@@ -0,0 +1,26 @@
+use std::fmt;
+
+/// A position in source text, as `line:column`, plus the byte `offset`
+/// of that position in the original source.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+impl Location {
+    pub fn new(line: usize, column: usize, offset: usize) -> Location {
+        Location { line, column, offset }
+    }
+
+    pub fn start() -> Location {
+        Location::new(1, 0, 0)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
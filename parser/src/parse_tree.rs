@@ -0,0 +1,94 @@
+use crate::location::Location;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub items: Vec<Stmt>,
+}
+
+/// The body of an `if`/`else` branch. The parser only ever produces
+/// `Block` (a brace-less single-statement body is ambiguous with a call
+/// chain on the condition); `Single` remains available for hand-built or
+/// desugared ASTs. `hir::resolve` normalizes both to a plain `Vec<Stmt>`.
+#[derive(Debug, Clone)]
+pub enum Body {
+    Block(Vec<Stmt>),
+    Single(Box<Stmt>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Stmt {
+    Let {
+        name: String,
+        value: Expr,
+        location: Location,
+    },
+    Expr(Expr),
+    Return(Option<Expr>),
+    FunctionDef {
+        name: String,
+        params: Vec<Param>,
+        body: Vec<Stmt>,
+        location: Location,
+    },
+    If {
+        cond: Expr,
+        then_branch: Body,
+        else_branch: Option<Body>,
+        location: Location,
+    },
+    /// Placeholder for a statement the parser couldn't make sense of;
+    /// `location` marks where recovery kicked in.
+    Error(Location),
+}
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    IntLiteral(i64, Location),
+    FloatLiteral(f64, Location),
+    BoolLiteral(bool, Location),
+    StrLiteral(String, Location),
+    Ident(String, Location),
+    /// `name = value` (`op: None`) or `name op= value` (`op: Some`),
+    /// written as source syntax; `hir::resolve` desugars the latter.
+    Assign {
+        name: String,
+        op: Option<BinOp>,
+        value: Box<Expr>,
+        location: Location,
+    },
+    Binary {
+        op: BinOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+        location: Location,
+    },
+    Call {
+        callee: Box<Expr>,
+        args: Vec<Expr>,
+        location: Location,
+    },
+    Block(Vec<Stmt>, Location),
+    /// Placeholder for an expression the parser couldn't make sense of.
+    Error(Location),
+}
@@ -0,0 +1,87 @@
+use lalrpop_util::{ErrorRecovery, ParseError};
+
+use crate::charj;
+use crate::error::Diagnostic;
+use crate::lexer::{LexError, Lexer};
+use crate::location::Location;
+use crate::parse_tree::Program;
+use crate::token::Token;
+
+/// Parse `source` into a `Program`, recovering from syntax errors at
+/// statement boundaries instead of stopping at the first one. Every
+/// problem encountered — recovered or not — comes back as a `Diagnostic`
+/// alongside the (possibly partial) tree.
+pub fn parse(source: &str) -> (Program, Vec<Diagnostic>) {
+    let mut recovered = Vec::new();
+    let lexer = Lexer::new(source);
+
+    let tree = match charj::ProgramParser::new().parse(&mut recovered, lexer) {
+        Ok(tree) => tree,
+        Err(error) => {
+            recovered.push(ErrorRecovery {
+                error,
+                dropped_tokens: Vec::new(),
+            });
+            Program { items: Vec::new() }
+        }
+    };
+
+    let diagnostics = recovered
+        .into_iter()
+        .map(|recovery| diagnostic_from_recovery(recovery.error))
+        .collect();
+
+    (tree, diagnostics)
+}
+
+fn diagnostic_from_recovery(error: ParseError<Location, Token, LexError>) -> Diagnostic {
+    match error {
+        ParseError::InvalidToken { location } => {
+            Diagnostic::error("invalid token", location)
+        }
+        ParseError::UnrecognizedEof { location, expected } => Diagnostic::error(
+            format!("unexpected end of input, expected one of: {}", expected.join(", ")),
+            location,
+        ),
+        ParseError::UnrecognizedToken { token: (start, token, _), expected } => Diagnostic::error(
+            format!(
+                "unexpected token `{:?}`, expected one of: {}",
+                token,
+                expected.join(", ")
+            ),
+            start,
+        ),
+        ParseError::ExtraToken { token: (start, token, _) } => {
+            Diagnostic::error(format!("unexpected extra token `{:?}`", token), start)
+        }
+        ParseError::User { error } => Diagnostic::error(error.message, error.location),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_syntax_errors_in_one_file_produce_two_diagnostics() {
+        let source = "let x = ;\nlet y = ;\n";
+        let (_tree, diagnostics) = parse(source);
+        assert_eq!(diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn valid_statements_around_a_syntax_error_still_parse() {
+        let source = "let x = 1;\nlet y = ;\nlet z = 2;\n";
+        let (tree, diagnostics) = parse(source);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(tree.items.len(), 3);
+    }
+
+    #[test]
+    fn clean_source_produces_no_diagnostics() {
+        let source = "let x = 1 + 2;\n";
+        let (tree, diagnostics) = parse(source);
+        assert!(diagnostics.is_empty());
+        assert_eq!(tree.items.len(), 1);
+    }
+}
@@ -0,0 +1,44 @@
+/// A lexical token. `LBracket`/`RBracket` aren't produced by any grammar
+/// production yet, but are lexed so delimiter-balance checks (the REPL's
+/// incomplete-input detection) see them like any other bracket.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Ident(String),
+    Int(i64),
+    Float(f64),
+    Str(String),
+
+    True,
+    False,
+    Let,
+    Fn,
+    Return,
+    If,
+    Else,
+
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Comma,
+    Semi,
+
+    Assign,
+    PlusAssign,
+    MinusAssign,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+
+    EqEq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    AndAnd,
+    OrOr,
+}
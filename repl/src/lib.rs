@@ -0,0 +1,6 @@
+extern crate interp;
+extern crate parser;
+
+mod repl;
+
+pub use repl::Repl;
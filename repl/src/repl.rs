@@ -0,0 +1,200 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use interp::{Interpreter, RuntimeError, Value};
+use parser::parse_tree::Stmt;
+use parser::token::Token;
+
+/// Outcome of feeding one line into the REPL.
+pub enum EvalOutcome {
+    /// The accumulated input parsed and ran. Carries the value of a bare
+    /// trailing expression, or `None` if the input was a statement.
+    Done(Option<Value>),
+    /// The input so far is an unterminated block or paren group; call
+    /// `eval_line` again with the next line appended.
+    Incomplete,
+}
+
+#[derive(Debug)]
+pub enum ReplError {
+    Parse(Vec<parser::error::Diagnostic>),
+    Runtime(RuntimeError),
+}
+
+impl fmt::Display for ReplError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplError::Parse(diagnostics) => {
+                for (i, d) in diagnostics.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{}", d.message)?;
+                }
+                Ok(())
+            }
+            ReplError::Runtime(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// A line-at-a-time REPL. Definitions made on one line stay in scope for
+/// later lines because they all share the same long-lived `Env`.
+pub struct Repl {
+    interpreter: Interpreter,
+    env: interp::Env,
+    buffer: String,
+}
+
+impl Repl {
+    pub fn new() -> Repl {
+        let interpreter = Interpreter::new();
+        let env = interpreter.globals.clone();
+        Repl {
+            interpreter,
+            env,
+            buffer: String::new(),
+        }
+    }
+
+    /// Feed `line` into the REPL, accounting for any input already
+    /// buffered from a previous incomplete line.
+    pub fn eval_line(&mut self, line: &str) -> Result<EvalOutcome, ReplError> {
+        if !self.buffer.is_empty() {
+            self.buffer.push('\n');
+        }
+        self.buffer.push_str(line);
+
+        let source = self.buffer.clone();
+        let (tree, diagnostics) = parser::parser::parse(&source);
+        if diagnostics.is_empty() {
+            self.buffer.clear();
+            self.run_stmts(&tree.items).map_err(ReplError::Runtime)
+        } else if input_is_incomplete(&source) {
+            Ok(EvalOutcome::Incomplete)
+        } else {
+            self.buffer.clear();
+            Err(ReplError::Parse(diagnostics))
+        }
+    }
+
+    fn run_stmts(&mut self, stmts: &[Stmt]) -> Result<EvalOutcome, RuntimeError> {
+        let mut trailing_value = None;
+        for stmt in stmts {
+            trailing_value = match stmt {
+                Stmt::Expr(expr) => Some(self.interpreter.eval_expr(expr, &self.env)?),
+                _ => {
+                    self.interpreter.exec_stmt(stmt, &self.env)?;
+                    None
+                }
+            };
+        }
+        Ok(EvalOutcome::Done(trailing_value))
+    }
+
+    /// Read lines from stdin until EOF, printing values and errors as
+    /// `eval_line` reports them.
+    pub fn run(&mut self) {
+        let stdin = io::stdin();
+        loop {
+            let prompt = if self.buffer.is_empty() { ">> " } else { ".. " };
+            print!("{}", prompt);
+            let _ = io::stdout().flush();
+
+            let mut line = String::new();
+            if stdin.read_line(&mut line).unwrap_or(0) == 0 {
+                break;
+            }
+            let line = line.trim_end_matches('\n');
+
+            match self.eval_line(line) {
+                Ok(EvalOutcome::Done(Some(value))) => println!("{}", value),
+                Ok(EvalOutcome::Done(None)) => {}
+                Ok(EvalOutcome::Incomplete) => {}
+                Err(err) => self.report(&err),
+            }
+        }
+    }
+
+    /// Print an error with a caret under its source column.
+    fn report(&self, err: &ReplError) {
+        match err {
+            ReplError::Parse(diagnostics) => {
+                for d in diagnostics {
+                    eprintln!("{}", d.message);
+                    eprintln!("{}^", " ".repeat(d.location.column));
+                }
+            }
+            ReplError::Runtime(e) => {
+                eprintln!("{}", e);
+                eprintln!("{}^", " ".repeat(e.location.column));
+            }
+        }
+    }
+}
+
+impl Default for Repl {
+    fn default() -> Repl {
+        Repl::new()
+    }
+}
+
+/// Whether `source` looks like it was cut off mid-block or mid-group,
+/// based on unterminated delimiters in its token stream.
+fn input_is_incomplete(source: &str) -> bool {
+    let tokens = match parser::lexer::lex(source) {
+        Ok(tokens) => tokens,
+        Err(_) => return false,
+    };
+
+    let mut depth = 0i32;
+    for token in &tokens {
+        match token {
+            Token::LParen | Token::LBrace | Token::LBracket => depth += 1,
+            Token::RParen | Token::RBrace | Token::RBracket => depth -= 1,
+            _ => {}
+        }
+    }
+    depth > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings_persist_across_lines() {
+        let mut repl = Repl::new();
+        assert!(matches!(repl.eval_line("let x = 1;"), Ok(EvalOutcome::Done(None))));
+        assert!(matches!(
+            repl.eval_line("x;"),
+            Ok(EvalOutcome::Done(Some(Value::Int(1))))
+        ));
+    }
+
+    #[test]
+    fn a_statement_has_no_trailing_value_but_an_expression_does() {
+        let mut repl = Repl::new();
+        assert!(matches!(repl.eval_line("let x = 1;"), Ok(EvalOutcome::Done(None))));
+        assert!(matches!(repl.eval_line("1 + 1;"), Ok(EvalOutcome::Done(Some(_)))));
+    }
+
+    #[test]
+    fn an_unterminated_block_is_buffered_until_the_closing_brace() {
+        let mut repl = Repl::new();
+        assert!(matches!(repl.eval_line("fn f() {"), Ok(EvalOutcome::Incomplete)));
+        assert!(matches!(
+            repl.eval_line("return 1;"),
+            Ok(EvalOutcome::Incomplete)
+        ));
+        assert!(matches!(repl.eval_line("}"), Ok(EvalOutcome::Done(None))));
+    }
+
+    #[test]
+    fn a_genuine_syntax_error_is_reported_and_clears_the_buffer() {
+        let mut repl = Repl::new();
+        assert!(matches!(repl.eval_line("let = 1;"), Err(ReplError::Parse(_))));
+        // The bad line shouldn't linger in the buffer and block later input.
+        assert!(matches!(repl.eval_line("1;"), Ok(EvalOutcome::Done(Some(_)))));
+    }
+}
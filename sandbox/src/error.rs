@@ -0,0 +1,45 @@
+use std::fmt;
+
+use interp::RuntimeError;
+use parser::location::Location;
+
+/// An error from running a program under a `Profile`.
+#[derive(Debug, Clone)]
+pub enum SandboxError {
+    /// The instruction or wall-clock budget ran out.
+    BudgetExceeded(Location),
+    /// The program attempted an operation the profile doesn't allow.
+    Violation { message: String, location: Location },
+    /// Any other runtime error, unrelated to sandboxing.
+    Runtime(RuntimeError),
+}
+
+impl SandboxError {
+    pub fn from_runtime(err: RuntimeError) -> SandboxError {
+        use interp::RuntimeErrorKind::*;
+        match err.kind {
+            BudgetExceeded => SandboxError::BudgetExceeded(err.location),
+            IoDenied => SandboxError::Violation {
+                message: err.message,
+                location: err.location,
+            },
+            General => SandboxError::Runtime(err),
+        }
+    }
+}
+
+impl fmt::Display for SandboxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SandboxError::BudgetExceeded(location) => {
+                write!(f, "instruction or time budget exceeded at {}", location)
+            }
+            SandboxError::Violation { message, location } => {
+                write!(f, "sandbox violation: {} at {}", message, location)
+            }
+            SandboxError::Runtime(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for SandboxError {}
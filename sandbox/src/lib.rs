@@ -0,0 +1,143 @@
+extern crate interp;
+extern crate parser;
+
+mod error;
+mod os_filter;
+mod profile;
+
+pub use error::SandboxError;
+pub use os_filter::apply as apply_os_filter;
+pub use profile::Profile;
+
+use std::cell::Cell;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Instant;
+
+use interp::{Interpreter, Value};
+
+/// Evaluate `program` under `profile`, denying any operation the profile
+/// doesn't allow and aborting once the instruction or wall-clock budget
+/// runs out.
+pub fn sandboxed_eval(
+    program: &parser::parse_tree::Program,
+    profile: &Profile,
+) -> Result<Value, SandboxError> {
+    let mut interpreter = Interpreter::new();
+    interpreter.budget.set(Some(profile.instruction_budget));
+    interpreter
+        .deadline
+        .set(Some(Instant::now() + profile.wall_clock_budget));
+    interpreter.io_hook = Some(make_io_hook(profile.clone()));
+
+    let env = interpreter.globals.clone();
+    let mut result = Value::Unit;
+    for stmt in &program.items {
+        if let parser::parse_tree::Stmt::Expr(expr) = stmt {
+            result = interpreter
+                .eval_expr(expr, &env)
+                .map_err(SandboxError::from_runtime)?;
+        } else {
+            interpreter
+                .exec_stmt(stmt, &env)
+                .map_err(SandboxError::from_runtime)?;
+        }
+    }
+    Ok(result)
+}
+
+/// Build the hook the interpreter consults before running any builtin,
+/// enforcing `profile`'s allow-list and `max_memory_bytes`. Memory is
+/// bounded coarsely: the hook tallies the bytes each `read_file` would
+/// pull in and denies the call once the running total would exceed the
+/// cap, rather than tracking the interpreter's actual heap usage.
+fn make_io_hook(profile: Profile) -> interp::IoHook {
+    let bytes_read = Rc::new(Cell::new(0usize));
+    Rc::new(move |op, args, location| match op {
+        "read_file" => {
+            let path = match args.first() {
+                Some(Value::Str(path)) => path,
+                _ => return Err(interp::RuntimeError::io_denied(op, location)),
+            };
+            if !profile.allows_read(Path::new(path)) {
+                return Err(interp::RuntimeError::io_denied(op, location));
+            }
+            let size = std::fs::metadata(path).map(|meta| meta.len() as usize).unwrap_or(0);
+            let total = bytes_read.get().saturating_add(size);
+            if total > profile.max_memory_bytes {
+                return Err(interp::RuntimeError::io_denied(
+                    "read_file (would exceed memory budget)",
+                    location,
+                ));
+            }
+            bytes_read.set(total);
+            Ok(())
+        }
+        _ => Err(interp::RuntimeError::io_denied(op, location)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_ok(source: &str) -> parser::parse_tree::Program {
+        let (program, diagnostics) = parser::parser::parse(source);
+        assert!(diagnostics.is_empty(), "unexpected parse errors: {:?}", diagnostics);
+        program
+    }
+
+    #[test]
+    fn exceeding_the_instruction_budget_aborts_the_run() {
+        let profile = Profile {
+            instruction_budget: 0,
+            ..Profile::locked_down()
+        };
+        let program = parse_ok("1;");
+
+        let result = sandboxed_eval(&program, &profile);
+        assert!(matches!(result, Err(SandboxError::BudgetExceeded(_))));
+    }
+
+    #[test]
+    fn reading_outside_the_allowed_prefix_is_denied() {
+        let profile = Profile::locked_down();
+        let program = parse_ok("read_file(\"/etc/hostname\");");
+
+        let result = sandboxed_eval(&program, &profile);
+        assert!(matches!(result, Err(SandboxError::Violation { .. })));
+    }
+
+    #[test]
+    fn reading_an_allowed_file_returns_its_contents() {
+        let path = std::env::temp_dir().join("charj_sandbox_test_allowed.txt");
+        std::fs::write(&path, b"hello").unwrap();
+
+        let profile = Profile {
+            read_prefix: Some(std::env::temp_dir()),
+            ..Profile::locked_down()
+        };
+        let program = parse_ok(&format!("read_file(\"{}\");", path.display()));
+
+        let result = sandboxed_eval(&program, &profile);
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(result, Ok(Value::Str(ref s)) if s == "hello"));
+    }
+
+    #[test]
+    fn reading_a_file_that_exceeds_max_memory_bytes_is_denied() {
+        let path = std::env::temp_dir().join("charj_sandbox_test_toolarge.txt");
+        std::fs::write(&path, vec![0u8; 64]).unwrap();
+
+        let profile = Profile {
+            read_prefix: Some(std::env::temp_dir()),
+            max_memory_bytes: 1,
+            ..Profile::locked_down()
+        };
+        let program = parse_ok(&format!("read_file(\"{}\");", path.display()));
+
+        let result = sandboxed_eval(&program, &profile);
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(result, Err(SandboxError::Violation { .. })));
+    }
+}
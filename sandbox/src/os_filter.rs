@@ -0,0 +1,23 @@
+//! Best-effort OS-level syscall filtering, meant to run just before a
+//! future codegen backend executes compiled output. There's no such
+//! backend yet, so `apply` is a no-op placeholder `sandboxed_eval` doesn't
+//! call into; it exists so that backend can wire it in without inventing
+//! a new entry point.
+
+#[cfg(target_os = "linux")]
+pub fn apply(_profile: &crate::Profile) -> std::io::Result<()> {
+    // A real implementation would install a seccomp-bpf filter here,
+    // denying everything outside `profile`'s allow-list.
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub fn apply(_profile: &crate::Profile) -> std::io::Result<()> {
+    // A real implementation would install a `sandbox_init` profile here.
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn apply(_profile: &crate::Profile) -> std::io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Describes what a sandboxed program is allowed to do. Network access is
+/// always denied; everything else defaults to "as restrictive as
+/// possible" so a caller has to opt in explicitly.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    /// If set, `read_file` may read any path under this directory.
+    /// If `None`, all file reads are denied.
+    pub read_prefix: Option<PathBuf>,
+    /// Caps the total bytes `read_file` may pull in over the run. This
+    /// bounds I/O-driven memory growth, not the interpreter's overall
+    /// heap (there's no allocator-level accounting yet).
+    pub max_memory_bytes: usize,
+    pub instruction_budget: u64,
+    pub wall_clock_budget: Duration,
+}
+
+impl Profile {
+    /// No filesystem access, a small instruction budget, and a short
+    /// wall-clock budget. The starting point for a profile a caller then
+    /// relaxes as needed.
+    pub fn locked_down() -> Profile {
+        Profile {
+            read_prefix: None,
+            max_memory_bytes: 16 * 1024 * 1024,
+            instruction_budget: 100_000,
+            wall_clock_budget: Duration::from_secs(1),
+        }
+    }
+
+    /// Resolves both `path` and `read_prefix` to their canonical form
+    /// before comparing, so a `..` component (or a symlink) can't walk
+    /// `path` outside the allowed prefix while still passing a textual
+    /// `starts_with` check. A path that doesn't exist, or a prefix that
+    /// doesn't canonicalize, is denied rather than risking a false allow.
+    pub fn allows_read(&self, path: &std::path::Path) -> bool {
+        let Some(prefix) = &self.read_prefix else {
+            return false;
+        };
+        let Ok(canonical_prefix) = std::fs::canonicalize(prefix) else {
+            return false;
+        };
+        let Ok(canonical_path) = std::fs::canonicalize(path) else {
+            return false;
+        };
+        canonical_path.starts_with(canonical_prefix)
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Profile {
+        Profile::locked_down()
+    }
+}